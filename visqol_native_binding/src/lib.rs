@@ -1,9 +1,19 @@
+// pyo3's `#[pymethods]`/`#[pyo3(signature = ...)]` expansion both mirrors
+// Python's full keyword-argument lists (more than clippy's default limit)
+// and emits a redundant `PyResult` conversion in its generated glue, so both
+// lints are silenced crate-wide rather than per call site.
+#![allow(clippy::too_many_arguments, clippy::useless_conversion)]
+
 use pyo3::prelude::*;
-use numpy::PyReadonlyArray1;
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1, PyReadonlyArray2};
+use numpy::ndarray::ArrayView2;
 use std::error::Error;
 use std::fmt;
 use tempfile::NamedTempFile;
 use hound::{WavWriter, WavSpec};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rubato::{Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction};
 
 // Import visqol-rs components - use public API only
 use visqol_rs::{
@@ -34,7 +44,6 @@ impl From<VisqolError> for PyErr {
 
 /// Python-friendly similarity result
 #[pyclass]
-#[derive(Clone)]
 pub struct SimilarityResult {
     #[pyo3(get)]
     pub moslqo: f64,
@@ -42,6 +51,15 @@ pub struct SimilarityResult {
     pub similarity_score: Option<f64>,
     #[pyo3(get)]
     pub processing_time: f64,
+    /// Per-frequency-band similarity (FVNSIM), one entry per ViSQOL band.
+    #[pyo3(get)]
+    pub fvnsim: Py<PyArray1<f64>>,
+    /// Per-patch band similarity, one row (as a band-similarity array) per patch.
+    #[pyo3(get)]
+    pub patch_similarity: Vec<Py<PyArray1<f64>>>,
+    /// Per-patch scalar similarity score, one entry per patch.
+    #[pyo3(get)]
+    pub patch_similarity_score: Vec<f64>,
 }
 
 #[pymethods]
@@ -54,18 +72,106 @@ impl SimilarityResult {
     }
 }
 
-impl From<RustSimilarityResult> for SimilarityResult {
-    fn from(rust_result: RustSimilarityResult) -> Self {
+impl SimilarityResult {
+    /// Convert a Rust ViSQOL result, materializing the per-band and per-patch
+    /// similarity vectors as numpy arrays.
+    ///
+    /// `patch_sims` holds one `PatchSimilarityResult` per patch (a scalar
+    /// `similarity`, per-band `freq_band_means`, and patch start/end time),
+    /// not a bare `Vec<f64>`, so both the scalar and the per-band means are
+    /// exposed per patch.
+    fn from_rust_result(py: Python<'_>, rust_result: RustSimilarityResult) -> Self {
+        let mut patch_similarity = Vec::with_capacity(rust_result.patch_sims.len());
+        let mut patch_similarity_score = Vec::with_capacity(rust_result.patch_sims.len());
+        for patch in rust_result.patch_sims {
+            patch_similarity_score.push(patch.similarity);
+            patch_similarity.push(patch.freq_band_means.into_pyarray_bound(py).unbind());
+        }
+
         SimilarityResult {
             moslqo: rust_result.moslqo,
             similarity_score: Some(rust_result.vnsim), // Use vnsim as similarity score
             processing_time: 0.0, // Will be set by caller
+            fvnsim: rust_result.fvnsim.into_pyarray_bound(py).unbind(),
+            patch_similarity,
+            patch_similarity_score,
         }
     }
 }
 
-/// Helper function to write audio data to a temporary WAV file
-fn write_audio_to_temp_file(audio_data: &[f64], sample_rate: u32) -> Result<NamedTempFile, Box<dyn Error>> {
+/// Resample `data` from `from_rate` to `to_rate` using a windowed-sinc
+/// polyphase resampler.
+fn sinc_resample(data: &[f64], from_rate: u32, to_rate: u32) -> Result<Vec<f64>, Box<dyn Error>> {
+    let params = SincInterpolationParameters {
+        sinc_len: 256,
+        f_cutoff: 0.95,
+        interpolation: SincInterpolationType::Linear,
+        oversampling_factor: 256,
+        window: WindowFunction::BlackmanHarris2,
+    };
+    let ratio = to_rate as f64 / from_rate as f64;
+    let mut resampler = SincFixedIn::<f64>::new(ratio, 2.0, params, data.len(), 1)?;
+    let waves_out = resampler.process(&[data.to_vec()], None)?;
+    Ok(waves_out.into_iter().next().unwrap_or_default())
+}
+
+/// Resample `data` to `target_rate` when `sample_rate` doesn't already match
+/// it. When `resample` is false and the rates mismatch, returns a clear error
+/// instead of silently writing audio at the wrong rate.
+fn resample_if_needed(
+    data: &[f64],
+    sample_rate: u32,
+    target_rate: u32,
+    resample: bool,
+) -> Result<Vec<f64>, VisqolError> {
+    if sample_rate == target_rate {
+        return Ok(data.to_vec());
+    }
+
+    if !resample {
+        return Err(VisqolError {
+            message: format!(
+                "Input sample rate {} Hz does not match the {} Hz required by this mode, and `resample` is disabled",
+                sample_rate, target_rate
+            ),
+        });
+    }
+
+    sinc_resample(data, sample_rate, target_rate).map_err(|e| VisqolError {
+        message: format!("Resampling from {} Hz to {} Hz failed: {}", sample_rate, target_rate, e),
+    })
+}
+
+/// Derive a per-pair dither seed for `calculate_batch`'s `index`-th pair from
+/// a base `seed`, so dither noise isn't identical across pairs while the
+/// whole batch stays reproducible for a given `seed`. `run_pair` further
+/// offsets the degraded channel's seed by 1, so this steps by 2 per pair to
+/// keep those derived seeds from colliding between pairs.
+fn batch_pair_seed(seed: Option<u64>, index: usize) -> Option<u64> {
+    seed.map(|s| s.wrapping_add(index as u64 * 2))
+}
+
+/// Round half away from zero, rather than `as i16`'s truncation toward zero.
+fn round_half_away_from_zero(x: f64) -> f64 {
+    if x >= 0.0 {
+        (x + 0.5).floor()
+    } else {
+        (x - 0.5).ceil()
+    }
+}
+
+/// Helper function to write audio data to a temporary WAV file.
+///
+/// When `dither` is set, a triangular-PDF dither (the sum of two independent
+/// uniform samples in `[-0.5, 0.5]`, i.e. spanning ±1 LSB) is added
+/// before rounding to de-correlate quantization error from the signal, which
+/// otherwise biases MOS-LQO on quiet material.
+fn write_audio_to_temp_file(
+    audio_data: &[f64],
+    sample_rate: u32,
+    dither: bool,
+    seed: Option<u64>,
+) -> Result<NamedTempFile, Box<dyn Error>> {
     let temp_file = NamedTempFile::new()?;
     let spec = WavSpec {
         channels: 1,
@@ -73,68 +179,243 @@ fn write_audio_to_temp_file(audio_data: &[f64], sample_rate: u32) -> Result<Name
         bits_per_sample: 16,
         sample_format: hound::SampleFormat::Int,
     };
-    
+
     let mut writer = WavWriter::create(temp_file.path(), spec)?;
-    
-    // Convert f64 to i16 and write
+
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
     for &sample in audio_data {
-        let sample_i16 = (sample.clamp(-1.0, 1.0) * 32767.0) as i16;
+        let scaled = sample.clamp(-1.0, 1.0) * 32767.0;
+        let dithered = if dither {
+            let tpdf_noise = (rng.gen::<f64>() - 0.5) + (rng.gen::<f64>() - 0.5);
+            scaled + tpdf_noise
+        } else {
+            scaled
+        };
+        let sample_i16 = round_half_away_from_zero(dithered).clamp(i16::MIN as f64, i16::MAX as f64) as i16;
         writer.write_sample(sample_i16)?;
     }
-    
+
     writer.finalize()?;
     Ok(temp_file)
 }
 
+/// Down-mix a multi-channel array to mono by averaging across the channel
+/// axis, matching ViSQOL's own contract that multi-channel input is
+/// down-mixed before comparison. Both `(channels, samples)` and
+/// `(samples, channels)` layouts are accepted.
+///
+/// `channels_first` says which axis holds channels; pass `None` to infer it
+/// from shape (the smaller dimension is assumed to be channels), which is
+/// ambiguous for square arrays and errors rather than silently guessing in
+/// that case. Short clips whose frame count happens to be less than or
+/// equal to the channel count are also inherently ambiguous by shape alone —
+/// callers working with such material should pass `channels_first` explicitly
+/// instead of relying on inference.
+fn downmix_to_mono(audio: &ArrayView2<f64>, channels_first: Option<bool>) -> Result<Vec<f64>, VisqolError> {
+    let (dim0, dim1) = audio.dim();
+    if dim0 == 0 || dim1 == 0 {
+        return Err(VisqolError {
+            message: "Multi-channel audio array cannot be empty".to_string(),
+        });
+    }
+
+    let channels_first = match channels_first {
+        Some(flag) => flag,
+        None if dim0 == dim1 => {
+            return Err(VisqolError {
+                message: format!(
+                    "Cannot infer the channel axis for a {}x{} array (equal dimensions); pass `channels_first` explicitly",
+                    dim0, dim1
+                ),
+            });
+        }
+        None => dim0 < dim1,
+    };
+    let (num_channels, num_frames) = if channels_first {
+        (dim0, dim1)
+    } else {
+        (dim1, dim0)
+    };
+
+    let mut mono = vec![0.0f64; num_frames];
+    for frame in 0..num_frames {
+        let mut sum = 0.0;
+        for channel in 0..num_channels {
+            sum += if channels_first {
+                audio[[channel, frame]]
+            } else {
+                audio[[frame, channel]]
+            };
+        }
+        mono[frame] = sum / num_channels as f64;
+    }
+    Ok(mono)
+}
+
+/// Model name that speech mode reports through `model_name` when no override
+/// is supplied. Without an override, speech mode scores through the speech
+/// mapper of `VisqolConfig::get_speech_mode_config` (see `speech_mode`),
+/// which does not read this file.
+const DEFAULT_SPEECH_SVR_MODEL: &str = "libsvm_nu_svr_model.txt";
+
+/// Default support-vector-regression model loaded by audio mode when no
+/// override is supplied, matching what `VisqolConfig::get_audio_mode_config`
+/// loads internally.
+const DEFAULT_AUDIO_SVR_MODEL: &str = "./model/libsvm_nu_svr_model.txt";
+
 /// Native ViSQOL calculator using Rust implementation
 #[pyclass(unsendable)]
 pub struct VisqolCalculator {
     manager: VisqolManager,
     mode: String,
+    target_sample_rate: u32,
+    model_name: String,
+}
+
+impl VisqolCalculator {
+    /// Shared mono pipeline: validate, write temp WAVs, and run the manager.
+    fn run_pair(
+        &mut self,
+        py: Python<'_>,
+        ref_data: &[f64],
+        deg_data: &[f64],
+        sample_rate: u32,
+        dither: bool,
+        seed: Option<u64>,
+        resample: bool,
+    ) -> PyResult<SimilarityResult> {
+        let start_time = std::time::Instant::now();
+
+        if ref_data.is_empty() {
+            return Err(VisqolError {
+                message: "Audio arrays cannot be empty".to_string(),
+            }
+            .into());
+        }
+
+        let ref_data = resample_if_needed(ref_data, sample_rate, self.target_sample_rate, resample)?;
+        let deg_data = resample_if_needed(deg_data, sample_rate, self.target_sample_rate, resample)?;
+        let target_sample_rate = self.target_sample_rate;
+
+        // Create temporary WAV files
+        let ref_temp_file = write_audio_to_temp_file(&ref_data, target_sample_rate, dither, seed)
+            .map_err(|e| VisqolError {
+                message: format!("Failed to create reference temp file: {}", e),
+            })?;
+
+        // Offset the degraded channel's seed so dither noise isn't identical
+        // between the two signals, while staying reproducible for a given seed.
+        let deg_temp_file = write_audio_to_temp_file(&deg_data, target_sample_rate, dither, seed.map(|s| s.wrapping_add(1)))
+            .map_err(|e| VisqolError {
+                message: format!("Failed to create degraded temp file: {}", e),
+            })?;
+
+        // Run ViSQOL calculation
+        let rust_result = self.manager
+            .run(
+                ref_temp_file.path().to_str().unwrap(),
+                deg_temp_file.path().to_str().unwrap(),
+            )
+            .map_err(|e| VisqolError {
+                message: format!("ViSQOL computation failed: {}", e),
+            })?;
+
+        let processing_time = start_time.elapsed().as_secs_f64();
+        let mut result = SimilarityResult::from_rust_result(py, rust_result);
+        result.processing_time = processing_time;
+
+        // Temp files are automatically cleaned up when dropped
+
+        Ok(result)
+    }
 }
 
 #[pymethods]
 impl VisqolCalculator {
-    /// Create a new ViSQOL calculator in speech mode (16kHz, optimized for speech)
+    /// Create a new ViSQOL calculator in speech mode (16kHz, optimized for
+    /// speech). Pass `model_path` to score with a domain-specific nu-SVR
+    /// model instead of the default polynomial MOS mapping, e.g. the
+    /// `tcdvoip` model tuned for telephony speech.
+    ///
+    /// `get_speech_mode_config()`'s `use_speech_mode: true` routes scoring
+    /// through `SpeechSimilarityToQualityMapper`, a fixed polynomial fit that
+    /// never reads `similarity_to_quality_model_path` — so setting that field
+    /// on top of the speech config would silently do nothing. A `model_path`
+    /// override therefore flips `use_speech_mode` off, which switches the
+    /// manager to `SvrSimilarityToQualityMapper` and actually loads the given
+    /// model; the 16kHz target rate is unaffected, since that's enforced by
+    /// this binding's own resampling, not by the config.
     #[staticmethod]
-    fn speech_mode() -> PyResult<Self> {
-        let config = VisqolConfig::get_speech_mode_config();
+    #[pyo3(signature = (model_path=None))]
+    fn speech_mode(model_path: Option<String>) -> PyResult<Self> {
+        let mut config = VisqolConfig::get_speech_mode_config();
+        if let Some(path) = model_path.as_deref() {
+            config.use_speech_mode = false;
+            config.similarity_to_quality_model_path = path.to_string();
+        }
         let manager = VisqolManager::from_config(&config);
-        
+
         Ok(VisqolCalculator {
             manager,
             mode: "speech".to_string(),
+            target_sample_rate: 16_000,
+            model_name: model_path.unwrap_or_else(|| DEFAULT_SPEECH_SVR_MODEL.to_string()),
         })
     }
-    
-    /// Create a new ViSQOL calculator in audio mode (48kHz, optimized for general audio)
+
+    /// Create a new ViSQOL calculator in audio mode (48kHz, optimized for general
+    /// audio). Loads the nu-SVR similarity-to-MOS mapping model (scored on the
+    /// ~1-4.75 range used by upstream ViSQOL's audio mode) from `model_path`, or
+    /// from the bundled default model when not given.
+    ///
+    /// `VisqolConfig::get_audio_mode_config()` already returns the right base
+    /// config (`use_speech_mode: false`, the bundled nu-SVR model path, a
+    /// 60-frame search window); only `similarity_to_quality_model_path` needs
+    /// overriding here when the caller supplies their own model. The 48kHz
+    /// target lives on `target_sample_rate` below, not on `VisqolConfig`
+    /// itself, since this crate's `VisqolConfig` has no `sample_rate` field —
+    /// the manager reads each WAV's actual rate from its header, and this
+    /// binding's own `resample_if_needed` is what enforces 48kHz.
     #[staticmethod]
-    fn audio_mode() -> PyResult<Self> {
-        // Use speech mode for now since audio mode needs model file path
-        // This is a limitation of the current visqol-rs public API
-        let config = VisqolConfig::get_speech_mode_config();
+    #[pyo3(signature = (model_path=None))]
+    fn audio_mode(model_path: Option<String>) -> PyResult<Self> {
+        let mut config = VisqolConfig::get_audio_mode_config();
+        if let Some(path) = model_path.as_deref() {
+            config.similarity_to_quality_model_path = path.to_string();
+        }
+        let model_name = model_path.unwrap_or_else(|| DEFAULT_AUDIO_SVR_MODEL.to_string());
+
         let manager = VisqolManager::from_config(&config);
-        
+
         Ok(VisqolCalculator {
             manager,
             mode: "audio".to_string(),
+            target_sample_rate: 48_000,
+            model_name,
         })
     }
-    
-    /// Calculate ViSQOL score for numpy arrays
+
+    /// Calculate ViSQOL score for numpy arrays. When `sample_rate` doesn't
+    /// match this mode's required rate, the audio is resampled (unless
+    /// `resample` is false, in which case a mismatch is an error).
+    #[pyo3(signature = (reference_audio, degraded_audio, sample_rate, dither=true, seed=None, resample=true))]
     fn calculate(
         &mut self,
+        py: Python<'_>,
         reference_audio: PyReadonlyArray1<f64>,
         degraded_audio: PyReadonlyArray1<f64>,
         sample_rate: u32,
+        dither: bool,
+        seed: Option<u64>,
+        resample: bool,
     ) -> PyResult<SimilarityResult> {
-        let start_time = std::time::Instant::now();
-        
-        // Convert numpy arrays to Rust slices
         let ref_data = reference_audio.as_slice()?;
         let deg_data = degraded_audio.as_slice()?;
-        
-        // Validate input
+
         if ref_data.len() != deg_data.len() {
             return Err(VisqolError {
                 message: format!(
@@ -145,52 +426,149 @@ impl VisqolCalculator {
             }
             .into());
         }
-        
-        if ref_data.is_empty() {
+
+        self.run_pair(py, ref_data, deg_data, sample_rate, dither, seed, resample)
+    }
+
+    /// Calculate ViSQOL score for multi-channel numpy arrays, shaped either
+    /// `(channels, samples)` or `(samples, channels)`. Each signal is
+    /// down-mixed to mono by averaging its channels before comparison, which
+    /// matches ViSQOL's own contract for multi-channel input.
+    ///
+    /// Pass `channels_first` to say explicitly which axis holds channels;
+    /// when omitted it's inferred from shape (the smaller dimension is
+    /// assumed to be channels), which is ambiguous for short clips or
+    /// near-square arrays — prefer passing it explicitly for such material.
+    #[pyo3(signature = (reference_audio, degraded_audio, sample_rate, dither=true, seed=None, resample=true, channels_first=None))]
+    fn calculate_multichannel(
+        &mut self,
+        py: Python<'_>,
+        reference_audio: PyReadonlyArray2<f64>,
+        degraded_audio: PyReadonlyArray2<f64>,
+        sample_rate: u32,
+        dither: bool,
+        seed: Option<u64>,
+        resample: bool,
+        channels_first: Option<bool>,
+    ) -> PyResult<SimilarityResult> {
+        let ref_mono = downmix_to_mono(&reference_audio.as_array(), channels_first)?;
+        let deg_mono = downmix_to_mono(&degraded_audio.as_array(), channels_first)?;
+
+        if ref_mono.len() != deg_mono.len() {
             return Err(VisqolError {
-                message: "Audio arrays cannot be empty".to_string(),
+                message: format!(
+                    "Reference and degraded audio must have the same frame count after down-mixing: {} vs {}",
+                    ref_mono.len(),
+                    deg_mono.len()
+                ),
             }
             .into());
         }
-        
-        // Create temporary WAV files
-        let ref_temp_file = write_audio_to_temp_file(ref_data, sample_rate)
-            .map_err(|e| VisqolError {
-                message: format!("Failed to create reference temp file: {}", e),
-            })?;
-        
-        let deg_temp_file = write_audio_to_temp_file(deg_data, sample_rate)
-            .map_err(|e| VisqolError {
-                message: format!("Failed to create degraded temp file: {}", e),
-            })?;
-        
-        // Run ViSQOL calculation
-        let rust_result = self.manager
-            .run(
-                ref_temp_file.path().to_str().unwrap(),
-                deg_temp_file.path().to_str().unwrap(),
-            )
-            .map_err(|e| VisqolError {
-                message: format!("ViSQOL computation failed: {}", e),
-            })?;
-        
-        let processing_time = start_time.elapsed().as_secs_f64();
-        let mut result = SimilarityResult::from(rust_result);
-        result.processing_time = processing_time;
-        
-        // Temp files are automatically cleaned up when dropped
-        
-        Ok(result)
+
+        self.run_pair(py, &ref_mono, &deg_mono, sample_rate, dither, seed, resample)
+    }
+
+    /// Evaluate a batch of (reference, degraded) numpy-array pairs against a
+    /// single manager instance, amortizing setup cost across the whole
+    /// dataset instead of re-creating a calculator per item.
+    #[pyo3(signature = (pairs, sample_rate, dither=true, seed=None, resample=true))]
+    fn calculate_batch(
+        &mut self,
+        py: Python<'_>,
+        pairs: Vec<(PyReadonlyArray1<f64>, PyReadonlyArray1<f64>)>,
+        sample_rate: u32,
+        dither: bool,
+        seed: Option<u64>,
+        resample: bool,
+    ) -> PyResult<BatchResult> {
+        let mut results = Vec::with_capacity(pairs.len());
+        for (index, (reference_audio, degraded_audio)) in pairs.into_iter().enumerate() {
+            let pair_seed = batch_pair_seed(seed, index);
+            let result = self.calculate(py, reference_audio, degraded_audio, sample_rate, dither, pair_seed, resample)?;
+            results.push(result);
+        }
+        Ok(BatchResult { results })
     }
-    
+
     /// Get the current mode (speech or audio)
     #[getter]
     fn mode(&self) -> &str {
         &self.mode
     }
-    
+
+    /// Get the MOS mapping model name recorded for this calculator: the
+    /// `model_path` passed to `speech_mode`/`audio_mode`, or the mode's
+    /// default model name.
+    #[getter]
+    fn model_name(&self) -> &str {
+        &self.model_name
+    }
+
     fn __repr__(&self) -> String {
-        format!("VisqolCalculator(mode='{}')", self.mode)
+        format!("VisqolCalculator(mode='{}', model='{}')", self.mode, self.model_name)
+    }
+}
+
+/// Results from a `calculate_batch` call, one `SimilarityResult` per input pair.
+#[pyclass]
+pub struct BatchResult {
+    pub results: Vec<SimilarityResult>,
+}
+
+#[pymethods]
+impl BatchResult {
+    /// `SimilarityResult` holds `Py<PyArray1<f64>>` handles, which aren't
+    /// `Clone` without a GIL token, so this field can't use `#[pyo3(get)]`
+    /// directly; clone each result's array handles here instead.
+    #[getter]
+    fn results(&self, py: Python<'_>) -> Vec<SimilarityResult> {
+        self.results
+            .iter()
+            .map(|r| SimilarityResult {
+                moslqo: r.moslqo,
+                similarity_score: r.similarity_score,
+                processing_time: r.processing_time,
+                fvnsim: r.fvnsim.clone_ref(py),
+                patch_similarity: r.patch_similarity.iter().map(|p| p.clone_ref(py)).collect(),
+                patch_similarity_score: r.patch_similarity_score.clone(),
+            })
+            .collect()
+    }
+
+    /// Write one row per pair to `path`, with `moslqo`, `vnsim`, and
+    /// `processing_time` columns.
+    fn to_csv(&self, path: String) -> PyResult<()> {
+        let mut writer = csv::Writer::from_path(&path).map_err(|e| VisqolError {
+            message: format!("Failed to create CSV at {}: {}", path, e),
+        })?;
+
+        writer
+            .write_record(["moslqo", "vnsim", "processing_time"])
+            .map_err(|e| VisqolError {
+                message: format!("Failed to write CSV header: {}", e),
+            })?;
+
+        for result in &self.results {
+            writer
+                .write_record([
+                    result.moslqo.to_string(),
+                    result.similarity_score.map(|v| v.to_string()).unwrap_or_default(),
+                    result.processing_time.to_string(),
+                ])
+                .map_err(|e| VisqolError {
+                    message: format!("Failed to write CSV row: {}", e),
+                })?;
+        }
+
+        writer.flush().map_err(|e| VisqolError {
+            message: format!("Failed to flush CSV to {}: {}", path, e),
+        })?;
+
+        Ok(())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("BatchResult({} pairs)", self.results.len())
     }
 }
 
@@ -199,10 +577,154 @@ impl VisqolCalculator {
 fn visqol_native(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<VisqolCalculator>()?;
     m.add_class::<SimilarityResult>()?;
-    
+    m.add_class::<BatchResult>()?;
+
     // Add version info
     m.add("__version__", "0.1.0")?;
     m.add("__author__", "Xingjian Du")?;
-    
+
     Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use numpy::ndarray::{array, Array2};
+
+    #[test]
+    fn downmix_to_mono_rejects_empty_array() {
+        let audio: Array2<f64> = Array2::zeros((2, 0));
+        let err = downmix_to_mono(&audio.view(), None).unwrap_err();
+        assert!(err.message.contains("empty"));
+    }
+
+    #[test]
+    fn downmix_to_mono_rejects_ambiguous_square_array_without_explicit_axis() {
+        let audio = array![[1.0, 2.0], [3.0, 4.0]];
+        let err = downmix_to_mono(&audio.view(), None).unwrap_err();
+        assert!(err.message.contains("Cannot infer the channel axis"));
+    }
+
+    #[test]
+    fn downmix_to_mono_averages_channels_first() {
+        let audio = array![[1.0, 2.0, 3.0], [3.0, 4.0, 5.0]];
+        let mono = downmix_to_mono(&audio.view(), Some(true)).unwrap();
+        assert_eq!(mono, vec![2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn downmix_to_mono_averages_channels_last() {
+        let audio = array![[1.0, 3.0], [2.0, 4.0], [3.0, 5.0]];
+        let mono = downmix_to_mono(&audio.view(), Some(false)).unwrap();
+        assert_eq!(mono, vec![2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn downmix_to_mono_infers_axis_from_shape_when_unambiguous() {
+        // 2 channels x 5 frames: the smaller dimension (channels) is assumed
+        // to be the channel axis when `channels_first` isn't given.
+        let audio = array![[1.0, 1.0, 1.0, 1.0, 1.0], [3.0, 3.0, 3.0, 3.0, 3.0]];
+        let mono = downmix_to_mono(&audio.view(), None).unwrap();
+        assert_eq!(mono, vec![2.0; 5]);
+    }
+
+    #[test]
+    fn round_half_away_from_zero_rounds_positive_halves_up() {
+        assert_eq!(round_half_away_from_zero(0.5), 1.0);
+        assert_eq!(round_half_away_from_zero(2.5), 3.0);
+    }
+
+    #[test]
+    fn round_half_away_from_zero_rounds_negative_halves_down() {
+        assert_eq!(round_half_away_from_zero(-0.5), -1.0);
+        assert_eq!(round_half_away_from_zero(-2.5), -3.0);
+    }
+
+    #[test]
+    fn round_half_away_from_zero_matches_plain_rounding_off_the_boundary() {
+        assert_eq!(round_half_away_from_zero(0.3), 0.0);
+        assert_eq!(round_half_away_from_zero(0.7), 1.0);
+        assert_eq!(round_half_away_from_zero(-0.3), 0.0);
+        assert_eq!(round_half_away_from_zero(-0.7), -1.0);
+    }
+
+    #[test]
+    fn write_audio_to_temp_file_without_dither_is_exact_round_trip() {
+        let samples = [0.0, 0.5, -0.5, 1.0, -1.0];
+        let temp_file = write_audio_to_temp_file(&samples, 16_000, false, None).unwrap();
+        let mut reader = hound::WavReader::open(temp_file.path()).unwrap();
+        let decoded: Vec<i16> = reader.samples::<i16>().map(|s| s.unwrap()).collect();
+        let expected: Vec<i16> = samples
+            .iter()
+            .map(|&s| round_half_away_from_zero(s * 32767.0) as i16)
+            .collect();
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn resample_if_needed_passes_through_when_rates_already_match() {
+        let data = vec![0.1, -0.2, 0.3];
+        let out = resample_if_needed(&data, 48_000, 48_000, false).unwrap();
+        assert_eq!(out, data);
+    }
+
+    #[test]
+    fn resample_if_needed_errors_on_mismatch_when_resampling_is_disabled() {
+        let data = vec![0.1, -0.2, 0.3];
+        let err = resample_if_needed(&data, 44_100, 48_000, false).unwrap_err();
+        assert!(err.message.contains("does not match"));
+    }
+
+    #[test]
+    fn resample_if_needed_changes_length_to_match_the_target_rate() {
+        // A half-second tone at 44.1kHz resampled to 48kHz should come back
+        // at roughly (not exactly, due to filter transients) half a second.
+        let samples = 22_050;
+        let data: Vec<f64> = (0..samples)
+            .map(|i| (i as f64 * 0.01).sin())
+            .collect();
+        let out = resample_if_needed(&data, 44_100, 48_000, true).unwrap();
+        let expected = (samples as f64 * 48_000.0 / 44_100.0) as usize;
+        let tolerance = expected / 10;
+        assert!(
+            out.len().abs_diff(expected) <= tolerance,
+            "resampled length {} too far from expected {}",
+            out.len(),
+            expected
+        );
+    }
+
+    #[test]
+    fn batch_pair_seed_is_none_without_a_base_seed() {
+        assert_eq!(batch_pair_seed(None, 0), None);
+        assert_eq!(batch_pair_seed(None, 5), None);
+    }
+
+    #[test]
+    fn batch_pair_seed_steps_by_two_per_pair() {
+        assert_eq!(batch_pair_seed(Some(10), 0), Some(10));
+        assert_eq!(batch_pair_seed(Some(10), 1), Some(12));
+        assert_eq!(batch_pair_seed(Some(10), 2), Some(14));
+    }
+
+    #[test]
+    fn batch_pair_seed_wraps_instead_of_overflowing() {
+        assert_eq!(batch_pair_seed(Some(u64::MAX), 1), Some(1));
+    }
+
+    #[test]
+    fn write_audio_to_temp_file_with_dither_is_deterministic_for_a_fixed_seed() {
+        let samples = [0.1, -0.2, 0.3, -0.4, 0.5];
+        let first = write_audio_to_temp_file(&samples, 16_000, true, Some(42)).unwrap();
+        let second = write_audio_to_temp_file(&samples, 16_000, true, Some(42)).unwrap();
+
+        let read = |path: &std::path::Path| -> Vec<i16> {
+            hound::WavReader::open(path)
+                .unwrap()
+                .samples::<i16>()
+                .map(|s| s.unwrap())
+                .collect()
+        };
+        assert_eq!(read(first.path()), read(second.path()));
+    }
 }
\ No newline at end of file